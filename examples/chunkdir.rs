@@ -33,6 +33,9 @@ fn main() {
     let mut total_count: u64 = 0;
     let mut files_processed = 0;
     let mut paths_skipped = 0;
+    let mut overall_min = usize::MAX;
+    let mut overall_max = 0usize;
+    let mut buckets: Vec<u64> = Vec::new();
 
     println!("Processing files under path: {}", walk_root);
     let start_time = PreciseTime::now();
@@ -60,10 +63,23 @@ fn main() {
         let as_slice = mmap.as_ref();
         match quickcdc::Chunker::with_params(as_slice, target_size, max_size, rng) {
             Ok(chunker) => {
-                for x in chunker {
+                let mut stats_chunker = chunker.with_stats();
+                for x in stats_chunker.by_ref() {
                     total_size += x.len() as u64;
                     total_count += 1;
                 }
+
+                let histogram = stats_chunker.histogram();
+                if histogram.count() > 0 {
+                    overall_min = overall_min.min(histogram.min());
+                    overall_max = overall_max.max(histogram.max());
+                    if buckets.is_empty() {
+                        buckets = vec![0u64; histogram.buckets().len()];
+                    }
+                    for (slot, &count) in buckets.iter_mut().zip(histogram.buckets()) {
+                        *slot += count;
+                    }
+                }
             }
             Err(e) => println!("Unable to create new chunker {:?}", e),
         }
@@ -79,6 +95,9 @@ fn main() {
     println!("Chunks Processed: {}", total_count);
     if total_count > 0 {
         println!("Average Chunk Size: {}", total_size / total_count);
+        println!("Min Chunk Size: {}", overall_min);
+        println!("Max Chunk Size: {}", overall_max);
+        println!("Chunk Size Histogram (buckets): {:?}", buckets);
     }
     println!("Total Bytes Processed: {}", total_size);
 }
@@ -0,0 +1,174 @@
+//! FastCDC-style chunker: a second, independent chunking algorithm alongside
+//! [`crate::Chunker`]'s AE implementation.
+//!
+//! Uses a gear hash rolling fingerprint with normalized chunking (NC) to
+//! tighten the chunk-size distribution relative to plain content-defined
+//! cutpoints. See [FastCDC: a Fast and Efficient Content-Defined Chunking
+//! Approach for Data Deduplication](https://www.usenix.org/system/files/conference/atc16/atc16-paper-xia.pdf).
+
+const GEAR_TABLE_SIZE: usize = 256;
+const NC_LEVEL: u32 = 2;
+const LCG_MULTIPLIER: u64 = 6364136223846793005;
+const LCG_INCREMENT: u64 = 1442695040888963407;
+
+#[derive(Debug)]
+pub struct FastCdcChunker<'a> {
+    slice: &'a [u8],
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    gear: [u64; GEAR_TABLE_SIZE],
+    mask_short: u64,
+    mask_long: u64,
+    bytes_processed: usize,
+    bytes_remaining: usize,
+}
+
+#[derive(Debug)]
+pub enum FastCdcError {
+    InsufficientMinSize,
+    InvalidSizeOrdering,
+}
+
+impl<'a> FastCdcChunker<'a> {
+    /// Given a {slice, min_size, avg_size, max_size, salt}, supply an iterable struct
+    /// that produces FastCDC chunked slices.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickcdc::FastCdcChunker;
+    ///
+    /// let sample = [0u8; 1024];
+    /// let chunker = FastCdcChunker::with_params(&sample[..], 64, 256, 1024, 15222894464462204665).unwrap();
+    /// for x in chunker {
+    ///     println!("{}", x.len());
+    /// }
+    /// ```
+    pub fn with_params(
+        slice: &'a [u8],
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+        salt: u64,
+    ) -> Result<FastCdcChunker<'a>, FastCdcError> {
+        if min_size < 64 {
+            return Err(FastCdcError::InsufficientMinSize);
+        }
+
+        if !(min_size < avg_size && avg_size < max_size) {
+            return Err(FastCdcError::InvalidSizeOrdering);
+        }
+
+        let gear = build_gear_table(salt);
+        let bits = (avg_size.next_power_of_two() - 1).count_ones();
+        let mask_short = build_mask(bits + NC_LEVEL, salt);
+        let mask_long = build_mask(bits.saturating_sub(NC_LEVEL), salt);
+
+        Ok(FastCdcChunker {
+            slice,
+            min_size,
+            avg_size,
+            max_size,
+            gear,
+            mask_short,
+            mask_long,
+            bytes_processed: 0,
+            bytes_remaining: slice.len(),
+        })
+    }
+}
+
+impl<'a> Iterator for FastCdcChunker<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.bytes_remaining == 0 {
+            return None;
+        }
+
+        let next_slice = next_fastcdc_slice(
+            &self.slice[(self.bytes_processed)..],
+            self.min_size,
+            self.avg_size,
+            self.max_size,
+            &self.gear,
+            self.mask_short,
+            self.mask_long,
+        );
+        self.bytes_processed += next_slice.len();
+        self.bytes_remaining -= next_slice.len();
+
+        Some(next_slice)
+    }
+}
+
+/// Return the next FastCDC-defined slice.
+fn next_fastcdc_slice<'a>(
+    remaining: &'a [u8],
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    gear: &[u64; GEAR_TABLE_SIZE],
+    mask_short: u64,
+    mask_long: u64,
+) -> &'a [u8] {
+    let remaining_bytes_length = remaining.len();
+
+    if remaining_bytes_length <= min_size {
+        return &remaining[..remaining_bytes_length];
+    }
+
+    let max_cut = if max_size < remaining_bytes_length {
+        max_size
+    } else {
+        remaining_bytes_length
+    };
+    let avg_cut = if avg_size < max_cut { avg_size } else { max_cut };
+
+    let mut fp: u64 = 0;
+
+    // Between min_size and avg_size, cut under the stricter mask (suppresses small chunks).
+    for i in min_size..avg_cut {
+        fp = (fp << 1).wrapping_add(gear[remaining[i] as usize]);
+        if fp & mask_short == 0 {
+            return &remaining[..=i];
+        }
+    }
+
+    // Between avg_size and max_size, cut under the looser mask.
+    for i in avg_cut..max_cut {
+        fp = (fp << 1).wrapping_add(gear[remaining[i] as usize]);
+        if fp & mask_long == 0 {
+            return &remaining[..=i];
+        }
+    }
+
+    // Max chunksize reached, force a cutpoint. This generally happens when processing
+    // data that doesn't change (e.g. sparse files / all zeros).
+    &remaining[..max_cut]
+}
+
+/// Build a 256-entry gear table from `salt` using an MMIX-style LCG.
+fn build_gear_table(salt: u64) -> [u64; GEAR_TABLE_SIZE] {
+    let mut table = [0u64; GEAR_TABLE_SIZE];
+    let mut v = salt;
+    for slot in table.iter_mut() {
+        v = v.wrapping_mul(LCG_MULTIPLIER).wrapping_add(LCG_INCREMENT);
+        *slot = v;
+    }
+    table
+}
+
+/// Build a mask with exactly `popcount` bits set (capped at 64, since a `u64` can't
+/// hold more), using the same LCG stream (seeded distinctly from the gear table so
+/// the two don't correlate).
+fn build_mask(popcount: u32, salt: u64) -> u64 {
+    let popcount = popcount.min(64);
+    let mut mask: u64 = 0;
+    let mut v = salt ^ 0xA5A5_A5A5_A5A5_A5A5;
+    while mask.count_ones() < popcount {
+        v = v.wrapping_mul(LCG_MULTIPLIER).wrapping_add(LCG_INCREMENT);
+        mask = (mask | 1).rotate_left((v as u32) & 0x3f);
+    }
+    mask
+}
@@ -0,0 +1,298 @@
+//! Per-chunk digesting and a writable chunk-index file format, so a chunker
+//! run can directly back a dedup/backup store: each chunk is identified by
+//! its digest, and two index files (from two runs over a changed file) can
+//! be diffed at chunk granularity.
+//!
+//! An index file is a fixed 4096-byte header (magic, archive UUID, creation
+//! time, total input size, chunking parameters, and a running checksum over
+//! the concatenated chunk digests) followed by the sequence of
+//! `(offset, length, digest)` entries.
+
+extern crate memmap;
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use memmap::Mmap;
+
+pub const INDEX_MAGIC: [u8; 8] = *b"QCDCIDX1";
+pub const INDEX_HEADER_SIZE: usize = 4096;
+pub const DIGEST_SIZE: usize = 32;
+const ENTRY_SIZE: usize = 8 + 8 + DIGEST_SIZE;
+
+/// Digest algorithm used to identify chunks, selected at build time via feature flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    #[cfg(feature = "sha256")]
+    Sha256,
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+impl DigestAlgorithm {
+    fn tag(self) -> u8 {
+        match self {
+            #[cfg(feature = "sha256")]
+            DigestAlgorithm::Sha256 => 1,
+            #[cfg(feature = "blake3")]
+            DigestAlgorithm::Blake3 => 2,
+        }
+    }
+}
+
+/// Digest `data` with `algorithm`. Both supported algorithms produce exactly
+/// [`DIGEST_SIZE`] bytes; no padding is applied.
+pub fn digest(algorithm: DigestAlgorithm, data: &[u8]) -> [u8; DIGEST_SIZE] {
+    match algorithm {
+        #[cfg(feature = "sha256")]
+        DigestAlgorithm::Sha256 => {
+            use sha2::{Digest as _, Sha256};
+            let mut hasher = Sha256::new();
+            hasher.update(data);
+            let result = hasher.finalize();
+            let mut out = [0u8; DIGEST_SIZE];
+            out.copy_from_slice(&result);
+            out
+        }
+        #[cfg(feature = "blake3")]
+        DigestAlgorithm::Blake3 => *blake3::hash(data).as_bytes(),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IndexEntry {
+    pub offset: u64,
+    pub length: u64,
+    pub digest: [u8; DIGEST_SIZE],
+}
+
+#[derive(Debug)]
+pub enum IndexError {
+    Io(io::Error),
+    BadMagic,
+    Truncated,
+    ChecksumMismatch,
+}
+
+impl From<io::Error> for IndexError {
+    fn from(e: io::Error) -> IndexError {
+        IndexError::Io(e)
+    }
+}
+
+/// Accumulates digested chunk entries as a chunker run progresses, then writes
+/// a complete index file.
+#[derive(Debug)]
+pub struct IndexBuilder {
+    algorithm: DigestAlgorithm,
+    target_chunksize: u64,
+    min_chunksize: u64,
+    max_chunksize: u64,
+    salt: u64,
+    total_size: u64,
+    entries: Vec<IndexEntry>,
+}
+
+impl IndexBuilder {
+    pub fn new(
+        algorithm: DigestAlgorithm,
+        target_chunksize: usize,
+        min_chunksize: usize,
+        max_chunksize: usize,
+        salt: u64,
+    ) -> IndexBuilder {
+        IndexBuilder {
+            algorithm,
+            target_chunksize: target_chunksize as u64,
+            min_chunksize: min_chunksize as u64,
+            max_chunksize: max_chunksize as u64,
+            salt,
+            total_size: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Digest `chunk` and record it at `offset` in the input.
+    pub fn push_chunk(&mut self, offset: u64, chunk: &[u8]) {
+        let digest = digest(self.algorithm, chunk);
+        self.total_size += chunk.len() as u64;
+        self.entries.push(IndexEntry {
+            offset,
+            length: chunk.len() as u64,
+            digest,
+        });
+    }
+
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// Serialize the fixed header followed by all recorded entries to `writer`.
+    pub fn write_to<W: Write>(&self, archive_id: [u8; 16], mut writer: W) -> io::Result<()> {
+        let mut header = [0u8; INDEX_HEADER_SIZE];
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let checksum = checksum_digests(&self.entries);
+
+        let mut pos = 0;
+        header[pos..pos + 8].copy_from_slice(&INDEX_MAGIC);
+        pos += 8;
+        header[pos] = self.algorithm.tag();
+        pos += 1;
+        pos += 7; // padding to the next 8-byte boundary
+        header[pos..pos + 16].copy_from_slice(&archive_id);
+        pos += 16;
+        write_u64(&mut header, &mut pos, created_at);
+        write_u64(&mut header, &mut pos, self.total_size);
+        write_u64(&mut header, &mut pos, self.target_chunksize);
+        write_u64(&mut header, &mut pos, self.min_chunksize);
+        write_u64(&mut header, &mut pos, self.max_chunksize);
+        write_u64(&mut header, &mut pos, self.salt);
+        write_u64(&mut header, &mut pos, self.entries.len() as u64);
+        write_u64(&mut header, &mut pos, checksum);
+
+        writer.write_all(&header)?;
+        for entry in &self.entries {
+            writer.write_all(&entry.offset.to_le_bytes())?;
+            writer.write_all(&entry.length.to_le_bytes())?;
+            writer.write_all(&entry.digest)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_u64(header: &mut [u8; INDEX_HEADER_SIZE], pos: &mut usize, value: u64) {
+    header[*pos..*pos + 8].copy_from_slice(&value.to_le_bytes());
+    *pos += 8;
+}
+
+fn checksum_digests(entries: &[IndexEntry]) -> u64 {
+    // FNV-1a over the concatenated digests.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for entry in entries {
+        for &byte in entry.digest.iter() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+/// A memory-mapped view over an index file written by [`IndexBuilder::write_to`].
+pub struct IndexReader {
+    mmap: Mmap,
+}
+
+impl IndexReader {
+    pub fn open(path: &Path) -> Result<IndexReader, IndexError> {
+        let file = File::open(path)?;
+        let mmap = unsafe { Mmap::map(&file) }?;
+        if mmap.len() < INDEX_HEADER_SIZE {
+            return Err(IndexError::Truncated);
+        }
+        if mmap[..8] != INDEX_MAGIC {
+            return Err(IndexError::BadMagic);
+        }
+        Ok(IndexReader { mmap })
+    }
+
+    fn read_u64(&self, pos: usize) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.mmap[pos..pos + 8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    pub fn archive_id(&self) -> [u8; 16] {
+        let mut id = [0u8; 16];
+        id.copy_from_slice(&self.mmap[16..32]);
+        id
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.read_u64(32)
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.read_u64(40)
+    }
+
+    pub fn target_chunksize(&self) -> u64 {
+        self.read_u64(48)
+    }
+
+    pub fn min_chunksize(&self) -> u64 {
+        self.read_u64(56)
+    }
+
+    pub fn max_chunksize(&self) -> u64 {
+        self.read_u64(64)
+    }
+
+    pub fn salt(&self) -> u64 {
+        self.read_u64(72)
+    }
+
+    pub fn entry_count(&self) -> u64 {
+        self.read_u64(80)
+    }
+
+    fn stored_checksum(&self) -> u64 {
+        self.read_u64(88)
+    }
+
+    /// Recompute the digest checksum over the stored entries and compare it against
+    /// the one recorded in the header.
+    pub fn verify_checksum(&self) -> Result<(), IndexError> {
+        let entries: Vec<IndexEntry> = self.entries().collect();
+        if checksum_digests(&entries) == self.stored_checksum() {
+            Ok(())
+        } else {
+            Err(IndexError::ChecksumMismatch)
+        }
+    }
+
+    /// Iterate over the `(offset, length, digest)` entries stored after the header.
+    pub fn entries(&self) -> IndexEntryIter<'_> {
+        IndexEntryIter {
+            mmap: &self.mmap,
+            pos: INDEX_HEADER_SIZE,
+            remaining: self.entry_count(),
+        }
+    }
+}
+
+pub struct IndexEntryIter<'a> {
+    mmap: &'a Mmap,
+    pos: usize,
+    remaining: u64,
+}
+
+impl<'a> Iterator for IndexEntryIter<'a> {
+    type Item = IndexEntry;
+
+    fn next(&mut self) -> Option<IndexEntry> {
+        if self.remaining == 0 || self.pos + ENTRY_SIZE > self.mmap.len() {
+            return None;
+        }
+
+        let mut offset_bytes = [0u8; 8];
+        offset_bytes.copy_from_slice(&self.mmap[self.pos..self.pos + 8]);
+        let mut length_bytes = [0u8; 8];
+        length_bytes.copy_from_slice(&self.mmap[self.pos + 8..self.pos + 16]);
+        let mut digest = [0u8; DIGEST_SIZE];
+        digest.copy_from_slice(&self.mmap[self.pos + 16..self.pos + ENTRY_SIZE]);
+
+        self.pos += ENTRY_SIZE;
+        self.remaining -= 1;
+
+        Some(IndexEntry {
+            offset: u64::from_le_bytes(offset_bytes),
+            length: u64::from_le_bytes(length_bytes),
+            digest,
+        })
+    }
+}
@@ -7,12 +7,32 @@
 //!
 //! This should be faster than many CDC algorithms (anecdotal performance: 2GB/s on an amd1950x with an NVMe drive), but faster alternatives exist.
 //! * For more information, see [FastCDC](https://www.usenix.org/system/files/conference/atc16/atc16-paper-xia.pdf)
+//! * A second algorithm based on that paper, [`FastCdcChunker`], is also provided: a gear-hash-based
+//!   chunker with normalized chunking (NC), producing a tighter chunk-size distribution than [`Chunker`].
 //!
 //! NOTE: This implementation performs much faster when built with `--release`.
 //!
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::cast_ptr_alignment))]
 
 extern crate rand;
+#[cfg(test)]
+#[macro_use]
+extern crate quickcheck;
+
+mod fastcdc;
+#[cfg(any(feature = "sha256", feature = "blake3"))]
+mod index;
+mod simd;
+mod stats;
+mod stream;
+
+pub use crate::fastcdc::{FastCdcChunker, FastCdcError};
+#[cfg(any(feature = "sha256", feature = "blake3"))]
+pub use crate::index::{
+    digest, DigestAlgorithm, IndexBuilder, IndexEntry, IndexError, IndexReader,
+};
+pub use crate::stats::{ChunkHistogram, StatsChunker};
+pub use crate::stream::StreamChunker;
 
 use rand::prelude::*;
 use std::f64::consts;
@@ -64,20 +84,11 @@ impl<'a> Chunker<'a> {
         max_chunksize_bytes: usize,
         salt: u64,
     ) -> Result<Chunker, ChunkerError> {
-        if 2 * target_chunksize_bytes > max_chunksize_bytes {
-            return Err(ChunkerError::InsufficientMaxSize);
-        }
-
-        if target_chunksize_bytes < 64 {
-            return Err(ChunkerError::InsufficientTargetSize);
-        }
-
-        let target_window_size = (target_chunksize_bytes as f64 / (consts::E - 1.0)) as usize;
-        let my_window_size = (target_window_size as f64 * 0.56) as usize;
-        let min_chunksize = target_chunksize_bytes - target_window_size;
+        let (window_size, min_chunksize) =
+            derive_window_params(target_chunksize_bytes, max_chunksize_bytes)?;
         let chunker: Chunker = Chunker {
             slice,
-            window_size: my_window_size,
+            window_size,
             salt,
             max_chunksize: max_chunksize_bytes,
             min_chunksize,
@@ -92,6 +103,24 @@ impl<'a> Chunker<'a> {
         let mut rng = rand::thread_rng();
         rng.next_u64()
     }
+
+    /// Wrap this chunker to additionally record the absolute byte offset of every
+    /// cutpoint and accumulate a chunk-size histogram, for benchmarking and tuning.
+    pub fn with_stats(self) -> StatsChunker<'a> {
+        StatsChunker::new(self)
+    }
+
+    /// The minimum chunk size derived from the `target_chunksize_bytes` passed to
+    /// [`Chunker::with_params`], below which the AE scan won't cut.
+    pub fn min_chunksize(&self) -> usize {
+        self.min_chunksize
+    }
+
+    /// The AE scan's window size, derived from the `target_chunksize_bytes` passed
+    /// to [`Chunker::with_params`].
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
 }
 
 /// Returns the next content-defined chunk.
@@ -117,6 +146,27 @@ impl<'a> Iterator for Chunker<'a> {
     }
 }
 
+/// Derive the (window_size, min_chunksize) pair used by the AE scan from the
+/// caller-supplied target/max chunk sizes, shared by both the slice-based and
+/// streaming chunkers so they produce identical cutpoints.
+fn derive_window_params(
+    target_chunksize_bytes: usize,
+    max_chunksize_bytes: usize,
+) -> Result<(usize, usize), ChunkerError> {
+    if 2 * target_chunksize_bytes > max_chunksize_bytes {
+        return Err(ChunkerError::InsufficientMaxSize);
+    }
+
+    if target_chunksize_bytes < 64 {
+        return Err(ChunkerError::InsufficientTargetSize);
+    }
+
+    let target_window_size = (target_chunksize_bytes as f64 / (consts::E - 1.0)) as usize;
+    let window_size = (target_window_size as f64 * 0.56) as usize;
+    let min_chunksize = target_chunksize_bytes - target_window_size;
+    Ok((window_size, min_chunksize))
+}
+
 /// Return the next content-defined slice.
 fn next_chunked_slice(
     remaining: &[u8],
@@ -133,32 +183,41 @@ fn next_chunked_slice(
     }
 
     let mut marker_position = 0;
+    let mut marker_value = simd::swapped_salted_value(remaining, 0) ^ salt;
     let end_index = remaining_bytes_length - SIZEOF_U64;
 
-    // Warp forward to avoid unnecessary processing
-    for i in min_chunksize..end_index {
-        // Max chunksize reached, force a cutpoint.
-        // This generally happens when processing data that doesn't change (e.g. sparse files / all zeros).
-        if i == max_chunksize {
-            return &remaining[..i];
+    // Warp forward to avoid unnecessary processing, LANES positions at a time: the
+    // per-position comparison stays sequential (the marker may only update at the
+    // first position it fails at), but the byte-swap+salt value for every position
+    // in the batch is computed together via `simd::swapped_salted_block`.
+    let mut i = min_chunksize;
+    while i < end_index {
+        let block = simd::swapped_salted_block(remaining, i, salt);
+        let block_len = simd::LANES.min(end_index - i);
+
+        for (k, &current_value) in block.iter().enumerate().take(block_len) {
+            let pos = i + k;
+
+            // Max chunksize reached, force a cutpoint.
+            // This generally happens when processing data that doesn't change (e.g. sparse files / all zeros).
+            if pos == max_chunksize {
+                return &remaining[..pos];
+            }
+
+            // Update marker position, if necessary
+            if current_value <= marker_value {
+                marker_position = pos;
+                marker_value = current_value;
+                continue;
+            }
+
+            // End of window reached without a new marker position, force a cutpoint
+            if pos == marker_position + window_size {
+                return &remaining[..pos];
+            }
         }
 
-        // Recast a pair of u64 pointers, to be used for comparison.
-        // Since 'i' never iterates beyond slice (i.e. remaining_bytes_length - SIZEOF_U64),
-        // we never dereference anything beyond the end of our slice.
-        let current_as_u64 = &remaining[i] as *const u8 as *const u64;
-        let marker_as_u64 = &remaining[marker_position] as *const u8 as *const u64;
-
-        // Update marker position, if necessary
-        if !swapped_salted_isgt(current_as_u64, marker_as_u64, salt) {
-            marker_position = i;
-            continue;
-        }
-
-        // End of window reached without a new marker position, force a cutpoint
-        if i == marker_position + window_size {
-            return &remaining[..i];
-        }
+        i += block_len;
     }
 
     // force a cutpoint
@@ -169,22 +228,3 @@ fn next_chunked_slice(
     };
     &remaining[..cutpoint]
 }
-
-/// Utility Function: Compare pointers to two 64-bit portions of data.
-///
-/// It does the following:
-/// * Dereferences each pointer into a u64 value.
-/// * Byte-swaps each value, and XOR the result with supplied salt.
-/// * Compare swapped+salted values, return comparison result.
-///
-/// De-referencing the pointers is an unsafe operation.  As long as the pointers do not extend beyond
-/// the end of the slice being chunked, this function will not result in undefined behavior.
-#[inline]
-fn swapped_salted_isgt(first: *const u64, second: *const u64, salt: u64) -> bool {
-    let compare_first = unsafe { (*first).swap_bytes() } ^ salt;
-    let compare_second = unsafe { (*second).swap_bytes() } ^ salt;
-    if compare_first > compare_second {
-        return true;
-    }
-    false
-}
@@ -0,0 +1,201 @@
+//! Vectorized fast path for the window scan in [`crate::next_chunked_slice`].
+//!
+//! The original scalar loop recast a single `*const u64` pointer per position and
+//! byte-swapped+salted it on the spot, which is the hot path the crate docs quote at
+//! ~2GB/s. Rather than recompute each position's byte-swapped+salted value one at a
+//! time, this module batches `LANES` consecutive positions per iteration: one
+//! unaligned 16-byte load, followed by a couple of `pshufb`/`tbl`-style byte
+//! shuffles that build all `LANES` overlapping 8-byte windows at once. The marker
+//! comparison itself stays scalar and sequential (it must: the marker can only
+//! update at the exact first position where the comparison fails), but the batch
+//! removes the redundant per-position pointer dereference + byte-swap + salt work.
+//!
+//! Because the comparison is order-sensitive, the vectorized path must produce
+//! byte-identical cutpoints to the scalar one; see the `simd_matches_scalar`
+//! quickcheck property below, which asserts equality between the two for
+//! arbitrary input and salt.
+//!
+//! Dispatch to the vectorized paths is a *runtime* check (`is_x86_feature_detected!`
+//! / `is_aarch64_feature_detected!`), not a compile-time `cfg(target_feature)` one:
+//! a plain `cargo build`/`cargo build --release` doesn't set `target-feature=+ssse3`
+//! etc, so gating on the cfg would leave the fast path dead on ordinary builds. The
+//! `#[target_feature(enable = ...)]` functions below exist precisely so they can be
+//! called behind a runtime check like this without requiring special `RUSTFLAGS`.
+
+/// Number of consecutive window values computed per vectorized batch. Chosen so a
+/// single 16-byte unaligned load covers every window in the batch: the last window
+/// in a batch of `LANES` needs bytes `[start + LANES - 1, start + LANES - 1 + 8)`.
+pub const LANES: usize = 4;
+
+/// The vectorized paths below all load a full 16-byte block starting at `start`
+/// regardless of how many of the `LANES` lanes the caller ultimately uses, so they
+/// require `start + 16 <= remaining.len()` to stay in bounds.
+const LOAD_SPAN: usize = 16;
+
+/// Compute the byte-swapped, salted `u64` value for each of `remaining[start..]`,
+/// `remaining[start + 1..]`, ... up to `LANES` consecutive starting offsets, using a
+/// platform SIMD fast path when available. Falls back to the scalar computation
+/// when there aren't enough trailing bytes for a full vector load, or on
+/// platforms without a supported fast path.
+///
+/// `remaining` must have at least `start + LANES + 7` bytes, except within `LANES`
+/// of the end of the slice, where fewer than `LANES` valid entries are returned.
+pub fn swapped_salted_block(remaining: &[u8], start: usize, salt: u64) -> [u64; LANES] {
+    #[allow(unused_variables)]
+    let available = remaining.len().saturating_sub(start);
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if available >= LOAD_SPAN {
+            if is_x86_feature_detected!("avx2") {
+                return unsafe { x86::swapped_salted_block_avx2(remaining, start, salt) };
+            }
+            if is_x86_feature_detected!("ssse3") {
+                return unsafe { x86::swapped_salted_block_ssse3(remaining, start, salt) };
+            }
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if available >= LOAD_SPAN && std::arch::is_aarch64_feature_detected!("neon") {
+            return unsafe { aarch64::swapped_salted_block_neon(remaining, start, salt) };
+        }
+    }
+
+    swapped_salted_block_scalar(remaining, start, salt)
+}
+
+/// Read `remaining[pos..pos + 8]` as a big-endian `u64` (on little-endian hosts,
+/// the same as a native-endian read followed by `.swap_bytes()`), unsalted -- the
+/// caller XORs the result with the salt. This is the scalar building block both
+/// the vectorized and fallback paths agree with.
+#[inline]
+pub fn swapped_salted_value(remaining: &[u8], pos: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&remaining[pos..pos + 8]);
+    u64::from_be_bytes(bytes)
+}
+
+fn swapped_salted_block_scalar(remaining: &[u8], start: usize, salt: u64) -> [u64; LANES] {
+    let mut out = [0u64; LANES];
+    let end_index = remaining.len().saturating_sub(8);
+    for (k, slot) in out.iter_mut().enumerate() {
+        let pos = start + k;
+        if pos > end_index {
+            break;
+        }
+        *slot = swapped_salted_value(remaining, pos) ^ salt;
+    }
+    out
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::LANES;
+    use std::arch::x86_64::*;
+    use std::convert::TryInto;
+
+    // For output lane k (0-indexed within a 16-byte `pshufb` shuffle), the byte
+    // written to output position j (j = 0 is the lowest memory address, i.e. the
+    // least-significant byte once reinterpreted as a little-endian u64) is input
+    // byte `k + 7 - j`, which puts the window's first memory byte in the most
+    // significant position -- exactly what `.swap_bytes()` does for a big-endian
+    // read on the scalar path.
+    const MASK_01: [u8; 16] = [7, 6, 5, 4, 3, 2, 1, 0, 8, 7, 6, 5, 4, 3, 2, 1];
+    const MASK_23: [u8; 16] = [9, 8, 7, 6, 5, 4, 3, 2, 10, 9, 8, 7, 6, 5, 4, 3];
+
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn swapped_salted_block_ssse3(
+        remaining: &[u8],
+        start: usize,
+        salt: u64,
+    ) -> [u64; LANES] {
+        let source = _mm_loadu_si128(remaining.as_ptr().add(start) as *const __m128i);
+        let mask01 = _mm_loadu_si128(MASK_01.as_ptr() as *const __m128i);
+        let mask23 = _mm_loadu_si128(MASK_23.as_ptr() as *const __m128i);
+
+        let shuffled01 = _mm_shuffle_epi8(source, mask01);
+        let shuffled23 = _mm_shuffle_epi8(source, mask23);
+
+        let mut bytes01 = [0u8; 16];
+        let mut bytes23 = [0u8; 16];
+        _mm_storeu_si128(bytes01.as_mut_ptr() as *mut __m128i, shuffled01);
+        _mm_storeu_si128(bytes23.as_mut_ptr() as *mut __m128i, shuffled23);
+
+        [
+            u64::from_le_bytes(bytes01[0..8].try_into().unwrap()) ^ salt,
+            u64::from_le_bytes(bytes01[8..16].try_into().unwrap()) ^ salt,
+            u64::from_le_bytes(bytes23[0..8].try_into().unwrap()) ^ salt,
+            u64::from_le_bytes(bytes23[8..16].try_into().unwrap()) ^ salt,
+        ]
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn swapped_salted_block_avx2(
+        remaining: &[u8],
+        start: usize,
+        salt: u64,
+    ) -> [u64; LANES] {
+        // AVX2's `vpshufb` only shuffles within each 128-bit lane, so this is just
+        // the SSSE3 path; a wider `LANES` would be needed to benefit from the extra
+        // 128 bits, which isn't worth the added complexity here.
+        swapped_salted_block_ssse3(remaining, start, salt)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use super::LANES;
+    use std::arch::aarch64::*;
+    use std::convert::TryInto;
+
+    const MASK_01: [u8; 16] = [7, 6, 5, 4, 3, 2, 1, 0, 8, 7, 6, 5, 4, 3, 2, 1];
+    const MASK_23: [u8; 16] = [9, 8, 7, 6, 5, 4, 3, 2, 10, 9, 8, 7, 6, 5, 4, 3];
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn swapped_salted_block_neon(
+        remaining: &[u8],
+        start: usize,
+        salt: u64,
+    ) -> [u64; LANES] {
+        let source = vld1q_u8(remaining.as_ptr().add(start));
+        let idx01 = vld1q_u8(MASK_01.as_ptr());
+        let idx23 = vld1q_u8(MASK_23.as_ptr());
+
+        let shuffled01 = vqtbl1q_u8(source, idx01);
+        let shuffled23 = vqtbl1q_u8(source, idx23);
+
+        let mut bytes01 = [0u8; 16];
+        let mut bytes23 = [0u8; 16];
+        vst1q_u8(bytes01.as_mut_ptr(), shuffled01);
+        vst1q_u8(bytes23.as_mut_ptr(), shuffled23);
+
+        [
+            u64::from_le_bytes(bytes01[0..8].try_into().unwrap()) ^ salt,
+            u64::from_le_bytes(bytes01[8..16].try_into().unwrap()) ^ salt,
+            u64::from_le_bytes(bytes23[0..8].try_into().unwrap()) ^ salt,
+            u64::from_le_bytes(bytes23[8..16].try_into().unwrap()) ^ salt,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    quickcheck! {
+        fn simd_matches_scalar(salt: u64, slice: Vec<u8>) -> bool {
+            if slice.len() < LANES + 7 {
+                return true;
+            }
+            for start in 0..=(slice.len() - LANES - 7) {
+                if swapped_salted_block(&slice, start, salt)
+                    != swapped_salted_block_scalar(&slice, start, salt)
+                {
+                    return false;
+                }
+            }
+            true
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Opt-in instrumentation for [`crate::Chunker`], useful when sweeping
+//! `target_size`/`salt` values for tuning: records the absolute byte offset
+//! of every cutpoint and accumulates a chunk-size histogram, without having
+//! to re-derive offsets from `chunk.len()` downstream.
+
+use crate::Chunker;
+
+const HISTOGRAM_BUCKET_COUNT: usize = 8;
+
+#[derive(Debug)]
+pub struct ChunkHistogram {
+    count: u64,
+    min: usize,
+    max: usize,
+    total: u64,
+    bucket_width: usize,
+    buckets: [u64; HISTOGRAM_BUCKET_COUNT],
+}
+
+impl ChunkHistogram {
+    fn new(max_chunksize: usize) -> ChunkHistogram {
+        ChunkHistogram {
+            count: 0,
+            min: usize::MAX,
+            max: 0,
+            total: 0,
+            bucket_width: (max_chunksize / HISTOGRAM_BUCKET_COUNT).max(1),
+            buckets: [0; HISTOGRAM_BUCKET_COUNT],
+        }
+    }
+
+    fn record(&mut self, size: usize) {
+        self.count += 1;
+        self.total += size as u64;
+        if size < self.min {
+            self.min = size;
+        }
+        if size > self.max {
+            self.max = size;
+        }
+        let bucket = (size / self.bucket_width).min(HISTOGRAM_BUCKET_COUNT - 1);
+        self.buckets[bucket] += 1;
+    }
+
+    /// Number of chunks recorded.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Smallest chunk size recorded, or 0 if none have been recorded yet.
+    pub fn min(&self) -> usize {
+        if self.count == 0 {
+            0
+        } else {
+            self.min
+        }
+    }
+
+    /// Largest chunk size recorded.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Mean chunk size, or 0.0 if none have been recorded yet.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total as f64 / self.count as f64
+        }
+    }
+
+    /// Per-bucket chunk counts, each bucket spanning `max_chunksize / bucket_count` bytes;
+    /// the last bucket also counts every larger size.
+    pub fn buckets(&self) -> &[u64] {
+        &self.buckets
+    }
+}
+
+/// Wraps a [`Chunker`], recording cut offsets and a size histogram as chunks are produced.
+#[derive(Debug)]
+pub struct StatsChunker<'a> {
+    inner: Chunker<'a>,
+    offset: u64,
+    cut_offsets: Vec<u64>,
+    histogram: ChunkHistogram,
+}
+
+impl<'a> StatsChunker<'a> {
+    pub(crate) fn new(inner: Chunker<'a>) -> StatsChunker<'a> {
+        let histogram = ChunkHistogram::new(inner.max_chunksize);
+        StatsChunker {
+            inner,
+            offset: 0,
+            cut_offsets: Vec::new(),
+            histogram,
+        }
+    }
+
+    /// Absolute byte offset of every cutpoint produced so far.
+    pub fn cut_offsets(&self) -> &[u64] {
+        &self.cut_offsets
+    }
+
+    /// Chunk-size distribution accumulated so far.
+    pub fn histogram(&self) -> &ChunkHistogram {
+        &self.histogram
+    }
+}
+
+impl<'a> Iterator for StatsChunker<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let next_slice = self.inner.next()?;
+        self.offset += next_slice.len() as u64;
+        self.cut_offsets.push(self.offset);
+        self.histogram.record(next_slice.len());
+        Some(next_slice)
+    }
+}
@@ -0,0 +1,102 @@
+//! Streaming variant of [`crate::Chunker`] that consumes any `std::io::Read`
+//! instead of requiring a fully buffered (or mmapped) `&[u8]`, so callers can
+//! chunk network streams, stdin, or files larger than RAM.
+
+use crate::{derive_window_params, next_chunked_slice, ChunkerError, SIZEOF_U64};
+use std::io::{self, Read};
+
+#[derive(Debug)]
+pub struct StreamChunker<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    window_size: usize,
+    max_chunksize: usize,
+    min_chunksize: usize,
+    salt: u64,
+    eof: bool,
+}
+
+impl<R: Read> StreamChunker<R> {
+    /// Given a {reader, target size, max_size, salt}, supply an iterable struct that
+    /// produces owned chunks read from `reader`.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickcdc::StreamChunker;
+    ///
+    /// let sample = [0u8; 1024];
+    /// let chunker = StreamChunker::with_params(&sample[..], 64, 128, 15222894464462204665).unwrap();
+    /// for x in chunker {
+    ///     println!("{}", x.unwrap().len());
+    /// }
+    /// ```
+    pub fn with_params(
+        reader: R,
+        target_chunksize_bytes: usize,
+        max_chunksize_bytes: usize,
+        salt: u64,
+    ) -> Result<StreamChunker<R>, ChunkerError> {
+        let (window_size, min_chunksize) =
+            derive_window_params(target_chunksize_bytes, max_chunksize_bytes)?;
+        Ok(StreamChunker {
+            reader,
+            buffer: Vec::new(),
+            window_size,
+            max_chunksize: max_chunksize_bytes,
+            min_chunksize,
+            salt,
+            eof: false,
+        })
+    }
+
+    /// Top up `self.buffer` until it holds enough bytes for the AE scan to reach the
+    /// same decision it would over a full in-memory slice, or until the reader is
+    /// exhausted.
+    fn refill(&mut self) -> io::Result<()> {
+        if self.eof {
+            return Ok(());
+        }
+
+        let target_len = self.max_chunksize + SIZEOF_U64;
+        let mut read_buf = [0u8; 65536];
+        while self.buffer.len() < target_len {
+            let bytes_read = match self.reader.read(&mut read_buf) {
+                Ok(n) => n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if bytes_read == 0 {
+                self.eof = true;
+                break;
+            }
+            self.buffer.extend_from_slice(&read_buf[..bytes_read]);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the next content-defined chunk, read fresh from the underlying reader.
+impl<R: Read> Iterator for StreamChunker<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<io::Result<Vec<u8>>> {
+        if let Err(e) = self.refill() {
+            return Some(Err(e));
+        }
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        let cut_len = next_chunked_slice(
+            &self.buffer,
+            self.window_size,
+            self.min_chunksize,
+            self.max_chunksize,
+            self.salt,
+        )
+        .len();
+
+        Some(Ok(self.buffer.drain(..cut_len).collect()))
+    }
+}
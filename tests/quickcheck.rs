@@ -92,5 +92,170 @@ mod tests {
 
         result_one == result_two
     }
+
+    fn fastcdc_chunker__given_any_salt__chunks_not_oversized(salt: u64, slice: Vec<u8>) -> bool {
+        let min_size = 64;
+        let avg_size = 256;
+        let max_size = 1024;
+        let chunker =
+            quickcdc::FastCdcChunker::with_params(&slice, min_size, avg_size, max_size, salt).unwrap();
+        for x in chunker {
+            if x.len() > max_size {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn stream_chunker__matches_slice_chunker(salt: u64, slice: Vec<u8>) -> bool {
+        let target_size = 64;
+        let max_size = 1024;
+
+        let slice_chunker =
+            quickcdc::Chunker::with_params(&slice, target_size, max_size, salt).unwrap();
+        let slice_result: Vec<Vec<u8>> = slice_chunker.map(|x| x.to_vec()).collect();
+
+        use std::io::Cursor;
+        let stream_chunker =
+            quickcdc::StreamChunker::with_params(Cursor::new(&slice), target_size, max_size, salt)
+                .unwrap();
+        let stream_result: Vec<Vec<u8>> = stream_chunker.map(|x| x.unwrap()).collect();
+
+        slice_result == stream_result
+    }
+
+    fn fastcdc_chunker__given_same_salt__returns_same_result(salt: u64, slice: Vec<u8>) -> bool {
+        let min_size = 64;
+        let avg_size = 256;
+        let max_size = 1024;
+        let chunker_one =
+            quickcdc::FastCdcChunker::with_params(&slice, min_size, avg_size, max_size, salt).unwrap();
+        let chunker_two =
+            quickcdc::FastCdcChunker::with_params(&slice, min_size, avg_size, max_size, salt).unwrap();
+
+        use std::collections::VecDeque;
+        let result_one: VecDeque<&[u8]> = chunker_one.collect();
+        let result_two: VecDeque<&[u8]> = chunker_two.collect();
+
+        result_one == result_two
+    }
+    }
+
+    #[test]
+    fn fastcdc_chunker__processing_zeroed_array__always_returns_max_chunk_size() {
+        let min_size = 64;
+        let avg_size = 256;
+        let max_size = 1024;
+        let zero_array = [0u8; 10240];
+
+        let chunker =
+            quickcdc::FastCdcChunker::with_params(&zero_array[..], min_size, avg_size, max_size, 0)
+                .unwrap();
+
+        for chunk in chunker {
+            assert_eq!(chunk.len(), max_size);
+        }
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn index_builder__writes_index_that_reader_can_verify() {
+        let target_size = 64;
+        let max_size = 1024;
+        let salt = 0;
+        let zero_array = [0u8; 10240];
+
+        let chunker =
+            quickcdc::Chunker::with_params(&zero_array[..], target_size, max_size, salt).unwrap();
+        let mut builder = quickcdc::IndexBuilder::new(
+            quickcdc::DigestAlgorithm::Blake3,
+            target_size,
+            chunker.min_chunksize(),
+            max_size,
+            salt,
+        );
+        let mut offset = 0u64;
+        for chunk in chunker {
+            builder.push_chunk(offset, chunk);
+            offset += chunk.len() as u64;
+        }
+
+        let path = std::env::temp_dir().join("quickcdc_index_builder_test.qcdcidx");
+        let file = std::fs::File::create(&path).unwrap();
+        builder.write_to([7u8; 16], file).unwrap();
+
+        let reader = quickcdc::IndexReader::open(&path).unwrap();
+        assert_eq!(reader.entry_count(), builder.entries().len() as u64);
+        assert_eq!(reader.total_size(), 10240);
+        assert!(reader.verify_checksum().is_ok());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[cfg(feature = "sha256")]
+    #[test]
+    fn index_builder__sha256__writes_index_that_reader_can_verify() {
+        let target_size = 64;
+        let max_size = 1024;
+        let salt = 0;
+        let zero_array = [0u8; 10240];
+
+        let chunker =
+            quickcdc::Chunker::with_params(&zero_array[..], target_size, max_size, salt).unwrap();
+        let mut builder = quickcdc::IndexBuilder::new(
+            quickcdc::DigestAlgorithm::Sha256,
+            target_size,
+            chunker.min_chunksize(),
+            max_size,
+            salt,
+        );
+        let mut offset = 0u64;
+        for chunk in chunker {
+            builder.push_chunk(offset, chunk);
+            offset += chunk.len() as u64;
+        }
+
+        let path = std::env::temp_dir().join("quickcdc_index_builder_sha256_test.qcdcidx");
+        let file = std::fs::File::create(&path).unwrap();
+        builder.write_to([7u8; 16], file).unwrap();
+
+        let reader = quickcdc::IndexReader::open(&path).unwrap();
+        assert_eq!(reader.entry_count(), builder.entries().len() as u64);
+        assert_eq!(reader.total_size(), 10240);
+        assert!(reader.verify_checksum().is_ok());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn stats_chunker__processing_zeroed_array__records_offsets_and_histogram() {
+        let target_size = 64;
+        let max_size = 1024;
+        let zero_array = [0u8; 10240];
+
+        let mut stats_chunker = quickcdc::Chunker::with_params(&zero_array[..], target_size, max_size, 0)
+            .unwrap()
+            .with_stats();
+        let chunk_count = stats_chunker.by_ref().count();
+
+        assert_eq!(chunk_count, 10);
+        assert_eq!(stats_chunker.cut_offsets().len(), 10);
+        assert_eq!(stats_chunker.cut_offsets().last(), Some(&10240));
+        assert_eq!(stats_chunker.histogram().count(), 10);
+        assert_eq!(stats_chunker.histogram().min(), max_size);
+        assert_eq!(stats_chunker.histogram().max(), max_size);
+    }
+
+    #[test]
+    fn fastcdc_chunker__when_given_invalid_size_ordering__returns_error() {
+        let min_size = 256;
+        let avg_size = 64; // must be greater than min_size
+        let max_size = 1024;
+        let zero_array = [0u8; 10240];
+
+        let chunker =
+            quickcdc::FastCdcChunker::with_params(&zero_array[..], min_size, avg_size, max_size, 0);
+
+        assert_eq!(chunker.is_err(), true);
     }
 }